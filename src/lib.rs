@@ -87,12 +87,126 @@
 //! let value = hodoku::expr!(Some(42)?);
 //! assert_eq!(value, 42);
 //! ```
-
-use std::array;
-use std::iter;
+//!
+//! ## Modes
+//!
+//! By default a rewritten `?` becomes `.unwrap()`. Passing `expect` as an
+//! argument instead rewrites it into `.expect(...)`, with the message built
+//! from the source text of the failing expression:
+//!
+//! ```
+//! # fn function() -> Result<u32, &'static str> { Ok(42) };
+//! #[hodoku::function(expect)]
+//! fn test_case() {
+//!     let value = function()?;
+//!     assert_eq!(value, 42);
+//! }
+//!
+//! test_case();
+//! ```
+//!
+//! A failing `function()?` here panics with `` `function()` failed`` rather
+//! than the generic `Result::unwrap()` message. `expr!` accepts the same mode
+//! as a leading argument, separated from the expression by a `;`:
+//!
+//! ```
+//! let value = hodoku::expr!(expect; Some(42)?);
+//! assert_eq!(value, 42);
+//! ```
+//!
+//! Passing `log` instead records the error through the [`log`] facade before
+//! unwrapping, so a later assertion failure still leaves a trail of which
+//! fallible call actually errored:
+//!
+//! ```ignore
+//! # fn function() -> Result<u32, &'static str> { Ok(42) };
+//! #[hodoku::function(log)]
+//! fn test_case() {
+//!     let value = function()?;
+//!     assert_eq!(value, 42);
+//! }
+//!
+//! test_case();
+//! ```
+//!
+//! `log` mode expands to a call to `log::error!`, so it requires `log` to be
+//! present as a dependency of the crate it's used in — unlike the other
+//! examples on this page, this one doesn't run as a doctest of `hodoku`
+//! itself.
+//!
+//! [`log`]: https://docs.rs/log
+//!
+//! ## Whole containers
+//!
+//! [`macro@module`] rewrites `?` in every function body contained in an
+//! `impl` block, a `trait` (including default method bodies), or a `mod`, so
+//! you don't have to annotate each method individually:
+//!
+//! ```
+//! struct Thing;
+//!
+//! #[hodoku::module]
+//! impl Thing {
+//!     fn one() -> u32 {
+//!         Some(1)?
+//!     }
+//!
+//!     fn two() -> u32 {
+//!         Some(2)?
+//!     }
+//! }
+//!
+//! assert_eq!(Thing::one(), 1);
+//! assert_eq!(Thing::two(), 2);
+//! ```
+//!
+//! Two escape hatches let a genuine `?`/early-return coexist with rewritten
+//! ones: [`raw!`] leaves its inner tokens untouched, and `#[hodoku::skip]`
+//! excludes the item it's attached to:
+//!
+//! ```
+//! struct Thing;
+//!
+//! #[hodoku::module]
+//! impl Thing {
+//!     fn rewritten() -> u32 {
+//!         Some(1)?
+//!     }
+//!
+//!     #[hodoku::skip]
+//!     fn untouched() -> Option<u32> {
+//!         let value = hodoku::raw!(Some(2)?);
+//!         Some(value)
+//!     }
+//! }
+//!
+//! assert_eq!(Thing::rewritten(), 1);
+//! assert_eq!(Thing::untouched(), Some(2));
+//! ```
+//!
+//! ## Reporting to non-Rust aggregators
+//!
+//! `report = "ruby"` (or `"python"`) additionally prints one synthesized
+//! stack frame in that language's convention before panicking, so an
+//! error-reporting backend that doesn't parse Rust backtraces can still
+//! cluster failures by call site:
+//!
+//! ```
+//! # fn function() -> Result<u32, &'static str> { Ok(42) };
+//! #[hodoku::function(report = "ruby")]
+//! fn test_case() {
+//!     let value = function()?;
+//!     assert_eq!(value, 42);
+//! }
+//!
+//! test_case();
+//! ```
+//!
+//! A failing `function()?` here prints something like
+//! `` from src/lib.rs:198:27:in 'test_case' `` to stderr before panicking.
 
 use proc_macro::Spacing;
-use proc_macro::{Delimiter, Group, Ident, Punct, TokenStream, TokenTree};
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Span, TokenStream, TokenTree};
 
 /// Process an expression or item marked with an attribute to modify any uses of
 /// the try operator `?` into trailing `.unwrap()`. So `Some(42)?` will be
@@ -100,6 +214,8 @@ use proc_macro::{Delimiter, Group, Ident, Punct, TokenStream, TokenTree};
 ///
 /// This is useful for adhoc testing.
 ///
+/// Takes an optional mode argument, see the [crate-level docs](crate#modes).
+///
 /// # Examples
 ///
 /// ```
@@ -113,11 +229,8 @@ use proc_macro::{Delimiter, Group, Ident, Punct, TokenStream, TokenTree};
 /// ```
 #[proc_macro_attribute]
 pub fn function(args: TokenStream, item: TokenStream) -> TokenStream {
-    if let Some(..) = args.into_iter().next() {
-        panic!("#[hodoku::function]: takes not arguments")
-    }
-
-    process(item)
+    let mode = parse_mode(args, "function");
+    process(item, mode)
 }
 
 /// Process an expression to modify any uses of the try operator `?` into
@@ -126,6 +239,9 @@ pub fn function(args: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// This is useful for adhoc testing.
 ///
+/// Takes an optional leading mode argument followed by `;`, see the
+/// [crate-level docs](crate#modes).
+///
 /// # Examples
 ///
 /// ```
@@ -134,48 +250,467 @@ pub fn function(args: TokenStream, item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn expr(input: TokenStream) -> TokenStream {
-    process(input)
+    let (mode_args, body) = split_expr_args(input);
+    let mode = parse_mode(mode_args, "expr");
+    process(body, mode)
+}
+
+/// Process every function body contained in an `impl` block, a `trait`
+/// (including default method bodies), or a `mod`, modifying any uses of the
+/// try operator `?` into trailing `.unwrap()`.
+///
+/// Use [`raw!`] or `#[hodoku::skip]` to exclude a `?`/function from being
+/// rewritten. Takes an optional mode argument, see the
+/// [crate-level docs](crate#modes).
+///
+/// # Examples
+///
+/// ```
+/// struct Thing;
+///
+/// #[hodoku::module]
+/// impl Thing {
+///     fn one() -> u32 {
+///         Some(1)?
+///     }
+/// }
+///
+/// assert_eq!(Thing::one(), 1);
+/// ```
+#[proc_macro_attribute]
+pub fn module(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mode = parse_mode(args, "module");
+    process(item, mode)
+}
+
+/// Exclude the item it's attached to from being rewritten by an enclosing
+/// [`macro@module`]. On its own this attribute is a no-op.
+#[proc_macro_attribute]
+pub fn skip(_args: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+/// Pass `input` through untouched, so a genuine `?`/early-return can coexist
+/// with rewritten ones inside a [`function`][macro@function] or
+/// [`macro@module`].
+#[proc_macro]
+pub fn raw(input: TokenStream) -> TokenStream {
+    input
+}
+
+/// Split `expr!` input into its optional leading mode arguments and the
+/// expression body, separated by a top-level `;`.
+fn split_expr_args(input: TokenStream) -> (TokenStream, TokenStream) {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+    let separator = tokens
+        .iter()
+        .position(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ';'));
+
+    match separator {
+        Some(index) => (
+            TokenStream::from_iter(tokens[..index].iter().cloned()),
+            TokenStream::from_iter(tokens[index + 1..].iter().cloned()),
+        ),
+        None => (TokenStream::new(), TokenStream::from_iter(tokens)),
+    }
+}
+
+/// How a rewritten `?` is expanded.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// `X?` becomes `X.unwrap()`.
+    Unwrap,
+    /// `X?` becomes `X.expect("`X` failed")`.
+    Expect,
+    /// `X?` becomes `X.map_err(|e| { log::error!(...); e }).unwrap()`.
+    Log,
+    /// `X?` becomes `X.unwrap_or_else(|e| { eprintln!(<frame>); panic!(...) })`.
+    Report(ReportFormat),
+}
+
+/// The aggregator convention a [`Mode::Report`] frame is formatted for.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    /// `from <file>:<line>:<col>:in '<fn>'`.
+    Ruby,
+    /// `File "<file>", line <line>, column <col>, in <fn>`.
+    Python,
 }
 
-fn process(item: TokenStream) -> TokenStream {
-    let mut it = item.into_iter();
-    let mut tmp = None::<array::IntoIter<TokenTree, 2>>;
+/// Parse the mode out of a macro's arguments, panicking with a message naming
+/// `name` (the macro in question) if the arguments can't be parsed.
+fn parse_mode(args: TokenStream, name: &str) -> Mode {
+    let mut it = args.into_iter();
 
-    TokenStream::from_iter(iter::from_fn(move || {
-        if let Some(buf) = tmp.as_mut() {
-            if let Some(tt) = buf.next() {
-                return Some(tt);
+    let mode = match it.next() {
+        None => Mode::Unwrap,
+        Some(TokenTree::Ident(ident)) => match ident.to_string().as_str() {
+            "expect" => Mode::Expect,
+            "log" => Mode::Log,
+            "report" => {
+                match it.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+                    _ => panic!(
+                        "#[hodoku::{}(report = \"...\")]: expected `=` after `report`",
+                        name
+                    ),
+                }
+
+                let literal = match it.next() {
+                    Some(TokenTree::Literal(literal)) => literal.to_string(),
+                    _ => panic!(
+                        "#[hodoku::{}(report = \"...\")]: expected a string literal",
+                        name
+                    ),
+                };
+
+                Mode::Report(match literal.trim_matches('"') {
+                    "ruby" => ReportFormat::Ruby,
+                    "python" => ReportFormat::Python,
+                    other => panic!(
+                        "#[hodoku::{}(report = \"{}\")]: unknown report format, expected `ruby` or `python`",
+                        name, other
+                    ),
+                })
             }
+            other => panic!("#[hodoku::{}]: unrecognized argument `{}`", name, other),
+        },
+        Some(tt) => panic!("#[hodoku::{}]: unrecognized argument `{}`", name, tt),
+    };
+
+    if let Some(tt) = it.next() {
+        panic!("#[hodoku::{}]: unexpected trailing argument `{}`", name, tt);
+    }
+
+    mode
+}
+
+/// Walk `item`, rewriting every `?` according to `mode`.
+///
+/// This buffers the trailing operand of each `?` — the contiguous run of
+/// tokens since the last boundary (`;`, `{`, `,`, `=`, `return`, or a binary
+/// operator) — so that modes which need the failing expression's source text
+/// (such as [`Mode::Expect`]) can stringify it.
+///
+/// Two escape hatches are recognized so rewriting can over-reach into a
+/// whole container (see [`macro@module`]) without touching everything: a
+/// `raw!(...)` call is passed through with its inner tokens untouched, and a
+/// `#[skip]`/`#[hodoku::skip]` attribute excludes the next brace-delimited
+/// body (a function, impl, or mod) from being recursed into.
+fn process(item: TokenStream, mode: Mode) -> TokenStream {
+    process_tokens(item, mode, None)
+}
+
+/// Like [`process`], additionally threading the name of the innermost
+/// enclosing function (discovered from `fn <name>` signatures as they're
+/// walked) so that [`Mode::Report`] can name it in its synthesized frame.
+fn process_tokens(item: TokenStream, mode: Mode, fn_name: Option<String>) -> TokenStream {
+    let tokens: Vec<TokenTree> = item.into_iter().collect();
+    let mut output: Vec<TokenTree> = Vec::with_capacity(tokens.len());
+    let mut operand_start = 0usize;
+    let mut skip_next_body = false;
+    let mut pending_fn_name = None::<String>;
+    let mut angle_depth = 0i32;
+    let mut i = 0;
 
-            tmp = None;
+    while i < tokens.len() {
+        if let (TokenTree::Ident(ident), Some(TokenTree::Punct(bang)), Some(TokenTree::Group(group))) =
+            (&tokens[i], tokens.get(i + 1), tokens.get(i + 2))
+        {
+            if ident.to_string() == "raw" && bang.as_char() == '!' {
+                output.push(TokenTree::Ident(ident.clone()));
+                output.push(TokenTree::Punct(bang.clone()));
+                output.push(TokenTree::Group(group.clone()));
+                i += 3;
+                continue;
+            }
         }
 
-        match it.next()? {
-            TokenTree::Group(g) => Some(TokenTree::Group(Group::new(
-                g.delimiter(),
-                process(g.stream()),
-            ))),
-            TokenTree::Punct(punct) => {
-                if punct.as_char() == '?' {
-                    let mut group = Group::new(Delimiter::Parenthesis, TokenStream::default());
-                    group.set_span(punct.span());
-
-                    tmp = Some(
-                        [
-                            TokenTree::Ident(Ident::new("unwrap", punct.span())),
-                            TokenTree::Group(group),
-                        ]
-                        .into_iter(),
-                    );
-
-                    let mut first = Punct::new('.', Spacing::Joint);
-                    first.set_span(punct.span());
-                    Some(TokenTree::Punct(first))
+        match tokens[i].clone() {
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(group)) = tokens.get(i + 1) {
+                    if group.delimiter() == Delimiter::Bracket && is_skip_attribute(group.stream())
+                    {
+                        output.push(TokenTree::Punct(p));
+                        output.push(TokenTree::Group(group.clone()));
+                        skip_next_body = true;
+                        operand_start = output.len();
+                        i += 2;
+                        continue;
+                    }
+                }
+                output.push(TokenTree::Punct(p));
+            }
+            TokenTree::Group(g) => {
+                let delimiter = g.delimiter();
+
+                if skip_next_body && delimiter == Delimiter::Brace {
+                    output.push(TokenTree::Group(Group::new(delimiter, g.stream())));
+                    skip_next_body = false;
                 } else {
-                    Some(TokenTree::Punct(punct))
+                    // Only a brace group immediately following a `fn <name>`
+                    // signature is that function's own body; other groups
+                    // (argument lists, nested blocks, ...) keep inheriting
+                    // whichever function currently encloses them.
+                    let next_fn = if delimiter == Delimiter::Brace {
+                        pending_fn_name.take().or_else(|| fn_name.clone())
+                    } else {
+                        fn_name.clone()
+                    };
+                    let mut new_group = Group::new(delimiter, process_tokens(g.stream(), mode, next_fn));
+                    new_group.set_span(g.span());
+                    output.push(TokenTree::Group(new_group));
+                }
+
+                if delimiter == Delimiter::Brace {
+                    operand_start = output.len();
+                }
+            }
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                output.push(TokenTree::Ident(ident));
+
+                if name == "fn" {
+                    if let Some(TokenTree::Ident(fn_ident)) = tokens.get(i + 1) {
+                        pending_fn_name = Some(fn_ident.to_string());
+                        output.push(TokenTree::Ident(fn_ident.clone()));
+                        i += 2;
+                        continue;
+                    }
+                } else if name == "return" {
+                    operand_start = i + 1;
+                }
+            }
+            TokenTree::Punct(p) if p.as_char() == '?' => {
+                let operand = TokenStream::from_iter(output[operand_start..].iter().cloned());
+                expand_try(&mut output, &operand, &p, mode, fn_name.as_deref());
+                operand_start = output.len();
+            }
+            // A turbofish (`::<...>`) or a nested generic inside one keeps
+            // its `<`, `,` and `>` as part of the operand rather than
+            // treating them as boundaries, so `"x".parse::<u32>()?` captures
+            // the whole call instead of just the trailing `()`.
+            TokenTree::Punct(p) if p.as_char() == '<' && (angle_depth > 0 || ends_with_path_sep(&output)) =>
+            {
+                angle_depth += 1;
+                output.push(TokenTree::Punct(p));
+            }
+            TokenTree::Punct(p) if p.as_char() == '>' && angle_depth > 0 => {
+                angle_depth -= 1;
+                output.push(TokenTree::Punct(p));
+            }
+            TokenTree::Punct(p) if p.as_char() == ',' && angle_depth > 0 => {
+                output.push(TokenTree::Punct(p));
+            }
+            TokenTree::Punct(p) => {
+                let boundary = is_boundary_punct(&p);
+                output.push(TokenTree::Punct(p));
+
+                if boundary {
+                    operand_start = output.len();
                 }
             }
-            tt => Some(tt),
+            tt => output.push(tt),
         }
-    }))
+
+        i += 1;
+    }
+
+    TokenStream::from_iter(output)
+}
+
+/// Whether `attr` (the content of a `#[...]`) marks an item to be skipped by
+/// an enclosing [`macro@module`]: either `skip` or `hodoku::skip`.
+fn is_skip_attribute(attr: TokenStream) -> bool {
+    let path = attr.to_string().replace(' ', "");
+    path == "skip" || path == "hodoku::skip"
+}
+
+/// Whether `p` is one of the boundary punctuation marks that end an operand:
+/// a statement/argument separator, an assignment, or a binary operator.
+fn is_boundary_punct(p: &Punct) -> bool {
+    matches!(
+        p.as_char(),
+        ';' | ',' | '=' | '+' | '-' | '*' | '/' | '%' | '<' | '>' | '!' | '&' | '|' | '^'
+    )
+}
+
+/// Whether `output` ends with a path separator `::`, i.e. the next `<` opens
+/// a turbofish rather than a comparison.
+fn ends_with_path_sep(output: &[TokenTree]) -> bool {
+    let Some(rest) = output.len().checked_sub(2) else {
+        return false;
+    };
+
+    matches!(&output[rest], TokenTree::Punct(p) if p.as_char() == ':')
+        && matches!(&output[rest + 1], TokenTree::Punct(p) if p.as_char() == ':')
+}
+
+/// Append the expansion of a single `?` (whose punct is `question`) to
+/// `output`, given the buffered source tokens of its operand and the name of
+/// the innermost enclosing function (used by [`Mode::Report`]).
+fn expand_try(
+    output: &mut Vec<TokenTree>,
+    operand: &TokenStream,
+    question: &Punct,
+    mode: Mode,
+    fn_name: Option<&str>,
+) {
+    let span = question.span();
+
+    let mut dot = Punct::new('.', Spacing::Joint);
+    dot.set_span(span);
+    output.push(TokenTree::Punct(dot));
+
+    match mode {
+        Mode::Unwrap => {
+            output.push(ident_tt("unwrap", span));
+            output.push(group_tt(Delimiter::Parenthesis, TokenStream::new(), span));
+        }
+        Mode::Expect => {
+            let message = format!("`{}` failed", operand);
+            output.push(ident_tt("expect", span));
+            output.push(group_tt(
+                Delimiter::Parenthesis,
+                TokenStream::from_iter([literal_tt(Literal::string(&message), span)]),
+                span,
+            ));
+        }
+        Mode::Log => {
+            output.push(ident_tt("map_err", span));
+            output.push(group_tt(
+                Delimiter::Parenthesis,
+                log_closure(&operand.to_string(), span),
+                span,
+            ));
+            let mut dot = Punct::new('.', Spacing::Joint);
+            dot.set_span(span);
+            output.push(TokenTree::Punct(dot));
+            output.push(ident_tt("unwrap", span));
+            output.push(group_tt(Delimiter::Parenthesis, TokenStream::new(), span));
+        }
+        Mode::Report(format) => {
+            output.push(ident_tt("unwrap_or_else", span));
+            output.push(group_tt(
+                Delimiter::Parenthesis,
+                report_closure(format, fn_name.unwrap_or("<unknown>"), span),
+                span,
+            ));
+        }
+    }
+}
+
+/// Build `|e| { log::error!("{}:{}: `{}` returned Err: {:?}", file!(), line!(), "<operand_src>", e); e }`,
+/// the closure passed to `.map_err(...)` in [`Mode::Log`].
+fn log_closure(operand_src: &str, span: Span) -> TokenStream {
+    let error_args = TokenStream::from_iter([
+        literal_tt(Literal::string("{}:{}: `{}` returned Err: {:?}"), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("file", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, TokenStream::new(), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("line", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, TokenStream::new(), span),
+        punct_tt(',', Spacing::Alone, span),
+        literal_tt(Literal::string(operand_src), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("e", span),
+    ]);
+
+    let body = TokenStream::from_iter([
+        ident_tt("log", span),
+        punct_tt(':', Spacing::Joint, span),
+        punct_tt(':', Spacing::Alone, span),
+        ident_tt("error", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, error_args, span),
+        punct_tt(';', Spacing::Alone, span),
+        ident_tt("e", span),
+    ]);
+
+    TokenStream::from_iter([
+        punct_tt('|', Spacing::Alone, span),
+        ident_tt("e", span),
+        punct_tt('|', Spacing::Alone, span),
+        group_tt(Delimiter::Brace, body, span),
+    ])
+}
+
+/// Build `|e| { eprintln!(<frame>, file!(), line!(), column!(), "<fn_name>"); panic!("{:?}", e) }`,
+/// the closure passed to `.unwrap_or_else(...)` in [`Mode::Report`]. `<frame>`
+/// is the aggregator-specific format for `format`.
+fn report_closure(format: ReportFormat, fn_name: &str, span: Span) -> TokenStream {
+    let frame = match format {
+        ReportFormat::Ruby => "from {}:{}:{}:in '{}'",
+        ReportFormat::Python => "File \"{}\", line {}, column {}, in {}",
+    };
+
+    let print_args = TokenStream::from_iter([
+        literal_tt(Literal::string(frame), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("file", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, TokenStream::new(), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("line", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, TokenStream::new(), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("column", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, TokenStream::new(), span),
+        punct_tt(',', Spacing::Alone, span),
+        literal_tt(Literal::string(fn_name), span),
+    ]);
+
+    let panic_args = TokenStream::from_iter([
+        literal_tt(Literal::string("{:?}"), span),
+        punct_tt(',', Spacing::Alone, span),
+        ident_tt("e", span),
+    ]);
+
+    let body = TokenStream::from_iter([
+        ident_tt("eprintln", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, print_args, span),
+        punct_tt(';', Spacing::Alone, span),
+        ident_tt("panic", span),
+        punct_tt('!', Spacing::Alone, span),
+        group_tt(Delimiter::Parenthesis, panic_args, span),
+    ]);
+
+    TokenStream::from_iter([
+        punct_tt('|', Spacing::Alone, span),
+        ident_tt("e", span),
+        punct_tt('|', Spacing::Alone, span),
+        group_tt(Delimiter::Brace, body, span),
+    ])
+}
+
+/// Build an identifier token with `span`.
+fn ident_tt(name: &str, span: Span) -> TokenTree {
+    TokenTree::Ident(Ident::new(name, span))
+}
+
+/// Build a punctuation token with `span`.
+fn punct_tt(c: char, spacing: Spacing, span: Span) -> TokenTree {
+    let mut punct = Punct::new(c, spacing);
+    punct.set_span(span);
+    TokenTree::Punct(punct)
+}
+
+/// Build a literal token with `span`.
+fn literal_tt(mut literal: Literal, span: Span) -> TokenTree {
+    literal.set_span(span);
+    TokenTree::Literal(literal)
+}
+
+/// Build a group delimited by `delimiter` containing `stream`, with `span`.
+fn group_tt(delimiter: Delimiter, stream: TokenStream, span: Span) -> TokenTree {
+    let mut group = Group::new(delimiter, stream);
+    group.set_span(span);
+    TokenTree::Group(group)
 }